@@ -0,0 +1,45 @@
+//! Actions dispatched by key bindings. Key chords are translated into
+//! `Action`s by the [`config`](crate::config) module so that components only
+//! ever react to intent, never to the physical key that produced it.
+
+use serde::Deserialize;
+
+use crate::config::Panel;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub enum Action {
+    Quit,
+    SelectNext,
+    SelectPrev,
+    /// Activate the focused component's current selection, e.g. picking a
+    /// branch in the Branches panel.
+    Confirm,
+    Squash,
+    FocusPanel(Panel),
+    /// Emitted by the Branches panel when a branch is confirmed; every
+    /// component sees it via `update` so the Commits panel can reload.
+    SelectBranch(String),
+    /// Emitted by the Commits panel whenever its selection changes, carrying
+    /// the selected commit's id (as hex, so the variant stays deserializable
+    /// like the rest of `Action`). The Commit info panel reloads on it.
+    SelectCommit(String),
+    /// Show/hide the keybinding help overlay.
+    ToggleHelp,
+}
+
+impl Action {
+    /// A short, human-readable description shown in the help overlay.
+    pub fn describe(&self) -> String {
+        match self {
+            Action::Quit => "Quit".to_string(),
+            Action::SelectNext => "Select next".to_string(),
+            Action::SelectPrev => "Select previous".to_string(),
+            Action::Confirm => "Confirm selection".to_string(),
+            Action::Squash => "Mark/squash the selected commit range".to_string(),
+            Action::FocusPanel(panel) => format!("Focus {panel:?}"),
+            Action::SelectBranch(_) => "Select branch".to_string(),
+            Action::SelectCommit(_) => "Select commit".to_string(),
+            Action::ToggleHelp => "Toggle this help".to_string(),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! Lists local branches and lets the user pick one to inspect.
+
+use git2::{BranchType, Repository};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    symbols::border,
+    text::Line,
+    widgets::{Block, List, ListItem, ListState},
+};
+
+use crate::action::Action;
+
+use super::Component;
+
+/// A branch as shown in the list: its short display name alongside the
+/// fully-qualified ref name (e.g. `"refs/heads/main"`) that `Action::SelectBranch`
+/// consumers need to call `repo.find_reference` with.
+#[derive(Debug, Clone)]
+struct BranchEntry {
+    name: String,
+    refname: String,
+}
+
+#[derive(Debug, Default)]
+pub struct Branches {
+    branches: Vec<BranchEntry>,
+    state: ListState,
+}
+
+impl Branches {
+    fn select_next(&mut self) {
+        let next = match self.state.selected() {
+            Some(i) if i + 1 < self.branches.len() => i + 1,
+            Some(i) => i,
+            None => 0,
+        };
+        self.state.select(Some(next));
+    }
+
+    fn select_prev(&mut self) {
+        let prev = self.state.selected().map_or(0, |i| i.saturating_sub(1));
+        self.state.select(Some(prev));
+    }
+}
+
+impl Component for Branches {
+    fn init(&mut self) -> color_eyre::Result<()> {
+        let repo = Repository::open_from_env()?;
+        self.branches = repo
+            .branches(Some(BranchType::Local))?
+            .filter_map(|branch_and_type| branch_and_type.ok())
+            .filter_map(|(branch, _type)| {
+                let name = branch.name().ok().flatten()?.to_string();
+                let refname = branch.get().name()?.to_string();
+                Some(BranchEntry { name, refname })
+            })
+            .collect();
+        if !self.branches.is_empty() {
+            self.state.select(Some(0));
+        }
+        Ok(())
+    }
+
+    fn handle_events(&mut self, action: Action) -> Option<Action> {
+        match action {
+            Action::SelectNext => {
+                self.select_next();
+                None
+            }
+            Action::SelectPrev => {
+                self.select_prev();
+                None
+            }
+            Action::Confirm => self
+                .state
+                .selected()
+                .and_then(|i| self.branches.get(i))
+                .map(|branch| Action::SelectBranch(branch.refname.clone())),
+            _ => None,
+        }
+    }
+
+    fn update(&mut self, _action: Action) {}
+
+    fn draw(&self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .branches
+            .iter()
+            .map(|branch| ListItem::new(branch.name.as_str()))
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::bordered()
+                    .title(Line::from("Branches").centered())
+                    .border_set(border::DOUBLE),
+            )
+            .highlight_symbol("> ");
+        frame.render_stateful_widget(list, area, &mut self.state.clone());
+    }
+}
@@ -0,0 +1,106 @@
+//! Shows the full message and diff of whatever commit is currently selected
+//! in the Commits panel.
+
+use git2::{DiffFormat, Oid, Repository};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    symbols::border,
+    text::Line,
+    widgets::{Block, Paragraph},
+};
+
+use crate::action::Action;
+
+use super::Component;
+
+#[derive(Debug, Default)]
+pub struct CommitInfo {
+    summary: String,
+    author: String,
+    message: String,
+    stats: String,
+    patch: String,
+}
+
+impl CommitInfo {
+    fn load(&mut self, repo: &Repository, oid: Oid) -> color_eyre::Result<()> {
+        let commit = repo.find_commit(oid)?;
+        let tree = commit.tree()?;
+        let parent_tree = commit.parent(0).ok().and_then(|parent| parent.tree().ok());
+        let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+        let stats = diff.stats()?;
+
+        let mut patch = String::new();
+        diff.print(DiffFormat::Patch, |_delta, _hunk, line| {
+            if let Ok(content) = std::str::from_utf8(line.content()) {
+                patch.push_str(content);
+            }
+            true
+        })?;
+
+        let author = commit.author();
+        self.summary = format!("{} {}", &oid.to_string()[..7], commit.summary().unwrap_or_default());
+        self.author = format!(
+            "{} <{}>",
+            author.name().unwrap_or_default(),
+            author.email().unwrap_or_default()
+        );
+        self.message = commit.message().unwrap_or_default().to_string();
+        self.stats = format!(
+            "{} file(s) changed, +{} -{}",
+            stats.files_changed(),
+            stats.insertions(),
+            stats.deletions()
+        );
+        self.patch = patch;
+        Ok(())
+    }
+}
+
+impl Component for CommitInfo {
+    fn init(&mut self) -> color_eyre::Result<()> {
+        let repo = Repository::open_from_env()?;
+        let head = repo.head()?.peel_to_commit()?;
+        self.load(&repo, head.id())
+    }
+
+    fn handle_events(&mut self, _action: Action) -> Option<Action> {
+        None
+    }
+
+    fn update(&mut self, action: Action) {
+        let Ok(repo) = Repository::open_from_env() else {
+            return;
+        };
+        match action {
+            Action::SelectCommit(oid) => {
+                if let Ok(oid) = Oid::from_str(&oid) {
+                    let _ = self.load(&repo, oid);
+                }
+            }
+            Action::SelectBranch(branch_ref) => {
+                if let Ok(tip) = repo
+                    .find_reference(&branch_ref)
+                    .and_then(|reference| reference.peel_to_commit())
+                {
+                    let _ = self.load(&repo, tip.id());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect) {
+        let text = format!(
+            "{}\n{}\n\n{}\n\n{}\n\n{}",
+            self.summary, self.author, self.message, self.stats, self.patch
+        );
+        let paragraph = Paragraph::new(text).block(
+            Block::bordered()
+                .title(Line::from("Commit info").centered())
+                .border_set(border::DOUBLE),
+        );
+        frame.render_widget(paragraph, area);
+    }
+}
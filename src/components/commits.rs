@@ -0,0 +1,282 @@
+//! Shows the selected branch's commit history and drives the squash
+//! workflow over it.
+
+use std::sync::mpsc;
+
+use git2::{Oid, Repository, Sort};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    symbols::border,
+    text::Line,
+    widgets::{Block, List, ListItem, ListState, Paragraph},
+};
+
+use crate::{
+    action::Action,
+    squash::{SquashError, SquashRange, squash_branch_range},
+};
+
+use super::Component;
+
+/// One row of the Commits list: the commit's id plus its already-formatted
+/// display label, so `draw` never has to touch the repository.
+#[derive(Debug, Clone)]
+struct CommitRow {
+    oid: Oid,
+    label: String,
+}
+
+#[derive(Debug, Default)]
+pub struct Commits {
+    branch_ref: String,
+    /// Branch history, newest first.
+    commits: Vec<CommitRow>,
+    state: ListState,
+    /// Index into `commits` marking the start of a squash selection.
+    squash_anchor: Option<usize>,
+    /// Last squash outcome, shown to the user instead of panicking or
+    /// leaving the repo half-rewritten.
+    status: Option<String>,
+    /// A squash running on its own thread so it doesn't block the render
+    /// loop, plus the row it should leave selected once it lands.
+    pending_squash: Option<(mpsc::Receiver<Result<Vec<CommitRow>, SquashError>>, usize)>,
+}
+
+impl Commits {
+    /// Walks the branch newest-first, sorted the same way
+    /// `squash::linear_history` sorts, so the indices the UI builds a
+    /// `SquashRange` from line up with what `squash_branch_range`
+    /// re-derives internally.
+    fn load(&mut self, branch_ref: &str) -> color_eyre::Result<()> {
+        let repo = Repository::open_from_env()?;
+        let tip = repo.find_reference(branch_ref)?.peel_to_commit()?;
+        self.commits = history_rows(&repo, tip.id())?;
+
+        self.branch_ref = branch_ref.to_string();
+        self.squash_anchor = None;
+        self.state.select((!self.commits.is_empty()).then_some(0));
+        Ok(())
+    }
+
+    fn selected_oid(&self) -> Option<Oid> {
+        self.state
+            .selected()
+            .and_then(|i| self.commits.get(i))
+            .map(|row| row.oid)
+    }
+
+    fn select_next(&mut self) {
+        let next = match self.state.selected() {
+            Some(i) if i + 1 < self.commits.len() => i + 1,
+            Some(i) => i,
+            None => 0,
+        };
+        self.state.select(Some(next));
+    }
+
+    fn select_prev(&mut self) {
+        let prev = self.state.selected().map_or(0, |i| i.saturating_sub(1));
+        self.state.select(Some(prev));
+    }
+
+    /// Mark `index` as the start/end of a contiguous squash selection, then
+    /// kick off squashing it into a single commit combining the original
+    /// messages, on its own thread so the render loop keeps going while the
+    /// cherry-pick replay runs.
+    fn squash_selected_range(&mut self, index: usize) {
+        if self.pending_squash.is_some() {
+            return;
+        }
+
+        let Some(anchor) = self.squash_anchor.take() else {
+            self.squash_anchor = Some(index);
+            return;
+        };
+
+        let (newest_idx, oldest_idx) = if anchor <= index {
+            (anchor, index)
+        } else {
+            (index, anchor)
+        };
+        let range = SquashRange {
+            oldest: self.commits[oldest_idx].oid,
+            newest: self.commits[newest_idx].oid,
+        };
+        let message = self.combined_squash_message(newest_idx, oldest_idx);
+
+        let (tx, rx) = mpsc::channel();
+        let branch_ref = self.branch_ref.clone();
+        std::thread::spawn(move || {
+            let outcome = (|| -> Result<Vec<CommitRow>, SquashError> {
+                let repo = Repository::open_from_env()?;
+                let new_tip = squash_branch_range(&repo, &branch_ref, range, &message)?;
+                Ok(history_rows(&repo, new_tip)?)
+            })();
+            let _ = tx.send(outcome);
+        });
+
+        self.pending_squash = Some((rx, newest_idx));
+        self.status = Some("squashing...".to_string());
+    }
+
+    fn selected_commit_action(&self) -> Option<Action> {
+        self.selected_oid()
+            .map(|oid| Action::SelectCommit(oid.to_string()))
+    }
+
+    fn combined_squash_message(&self, newest_idx: usize, oldest_idx: usize) -> String {
+        let repo = match Repository::open_from_env() {
+            Ok(repo) => repo,
+            Err(_) => return String::new(),
+        };
+        self.commits[newest_idx..=oldest_idx]
+            .iter()
+            .rev()
+            .filter_map(|row| repo.find_commit(row.oid).ok())
+            .filter_map(|commit| commit.message().map(str::to_string))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+impl Component for Commits {
+    fn init(&mut self) -> color_eyre::Result<()> {
+        let repo = Repository::open_from_env()?;
+        let branch_ref = repo.head()?.name().unwrap_or_default().to_string();
+        self.load(&branch_ref)
+    }
+
+    fn handle_events(&mut self, action: Action) -> Option<Action> {
+        match action {
+            Action::SelectNext => {
+                self.select_next();
+                self.selected_commit_action()
+            }
+            Action::SelectPrev => {
+                self.select_prev();
+                self.selected_commit_action()
+            }
+            Action::Squash if !self.commits.is_empty() => {
+                let index = self.state.selected().unwrap_or(0);
+                self.squash_selected_range(index);
+                self.selected_commit_action()
+            }
+            _ => None,
+        }
+    }
+
+    fn poll(&mut self) -> Option<Action> {
+        let (rx, newest_idx) = self.pending_squash.take()?;
+        let outcome = match rx.try_recv() {
+            Err(mpsc::TryRecvError::Empty) => {
+                self.pending_squash = Some((rx, newest_idx));
+                return None;
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.status = Some("squash failed: worker thread panicked".to_string());
+                return None;
+            }
+            Ok(outcome) => outcome,
+        };
+
+        self.status = Some(match outcome {
+            Ok(rows) => {
+                self.commits = rows;
+                // The squashed commit takes the range's former position: the
+                // same number of newer commits still sit above it.
+                let selected = newest_idx.min(self.commits.len().saturating_sub(1));
+                self.state
+                    .select((!self.commits.is_empty()).then_some(selected));
+                "squash complete".to_string()
+            }
+            Err(err) => format!("squash failed: {err}"),
+        });
+        self.selected_commit_action()
+    }
+
+    fn update(&mut self, action: Action) {
+        if let Action::SelectBranch(branch_ref) = action {
+            if let Err(err) = self.load(&branch_ref) {
+                self.status = Some(format!("failed to load {branch_ref}: {err}"));
+            }
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect) {
+        let mut block = Block::bordered()
+            .title(Line::from("Commits").centered())
+            .border_set(border::DOUBLE);
+        if let Some(status) = &self.status {
+            block = block.title_bottom(Line::from(status.as_str()));
+        }
+
+        if self.commits.is_empty() {
+            frame.render_widget(Paragraph::new("no commits").block(block), area);
+            return;
+        }
+
+        let items: Vec<ListItem> = self
+            .commits
+            .iter()
+            .map(|row| ListItem::new(row.label.as_str()))
+            .collect();
+
+        let list = List::new(items).block(block).highlight_symbol("> ");
+        frame.render_stateful_widget(list, area, &mut self.state.clone());
+    }
+}
+
+/// Walk `tip`'s history (newest-first, sorted the same way
+/// `squash::linear_history` sorts) and format each commit's display label up
+/// front, so `draw` never has to reopen the repo or call `find_commit` on
+/// every frame.
+fn history_rows(repo: &Repository, tip: Oid) -> Result<Vec<CommitRow>, git2::Error> {
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(tip)?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+
+    revwalk
+        .map(|oid| {
+            let oid = oid?;
+            let commit = repo.find_commit(oid)?;
+            let summary = commit.summary().unwrap_or_default();
+            let author = commit.author().name().unwrap_or_default().to_string();
+            let when = relative_time(commit.author().when());
+            let label = format!("{} {summary}  ({author}, {when})", &oid.to_string()[..7]);
+            Ok(CommitRow { oid, label })
+        })
+        .collect()
+}
+
+/// Render a `git2::Time` as a coarse "N units ago" string, the way `git log
+/// --relative-date` would.
+fn relative_time(time: git2::Time) -> String {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    const MINUTE: i64 = 60;
+    const HOUR: i64 = 60 * MINUTE;
+    const DAY: i64 = 24 * HOUR;
+    const WEEK: i64 = 7 * DAY;
+    const MONTH: i64 = 30 * DAY;
+    const YEAR: i64 = 365 * DAY;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let delta = (now - time.seconds()).max(0);
+
+    if delta < MINUTE {
+        return "just now".to_string();
+    }
+    let (value, unit) = match delta {
+        d if d < HOUR => (d / MINUTE, "minute"),
+        d if d < DAY => (d / HOUR, "hour"),
+        d if d < WEEK => (d / DAY, "day"),
+        d if d < MONTH => (d / WEEK, "week"),
+        d if d < YEAR => (d / MONTH, "month"),
+        d => (d / YEAR, "year"),
+    };
+    format!("{value} {unit}{} ago", if value == 1 { "" } else { "s" })
+}
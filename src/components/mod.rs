@@ -0,0 +1,41 @@
+//! Each panel of the UI owns its own state and input handling behind a
+//! common `Component` trait, instead of being rendered inline from `App`.
+
+pub mod branches;
+pub mod commit_info;
+pub mod commits;
+
+use ratatui::{Frame, layout::Rect};
+
+use crate::action::Action;
+
+/// A self-contained panel. `App` holds one of these per panel, routes the
+/// `Action` the keymap resolves to whichever is focused, then fans out
+/// whatever that returns to every component via `update` — so, for example,
+/// confirming a branch in the Branches panel can tell the Commits panel to
+/// reload.
+pub trait Component {
+    /// One-time setup, e.g. loading the git data a panel starts with.
+    fn init(&mut self) -> color_eyre::Result<()> {
+        Ok(())
+    }
+
+    /// Only called on the focused component. Returns an `Action` for `App`
+    /// to broadcast to every component via `update`, if handling this one
+    /// produced a new one.
+    fn handle_events(&mut self, action: Action) -> Option<Action>;
+
+    /// Called on every component for every action returned from
+    /// `handle_events`, regardless of which panel was focused.
+    fn update(&mut self, action: Action);
+
+    /// Called on every component on every `Tick`, so a component that kicked
+    /// off background work (e.g. a long-running squash) can check whether it
+    /// finished without blocking the render loop on it. Returns an `Action`
+    /// for `App` to broadcast the same way `handle_events` does.
+    fn poll(&mut self) -> Option<Action> {
+        None
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect);
+}
@@ -0,0 +1,270 @@
+//! Loads keybindings from a user config file instead of hardcoding key
+//! handling, so every action in the app can be rebound without recompiling.
+//!
+//! The file is RON, resolved as follows:
+//! 1. the `SQUASH_TUI_CONFIG` env var, if set, names the file directly;
+//! 2. otherwise `directories::ProjectDirs` gives us the platform config dir,
+//!    and we look for `keybindings.ron` inside it.
+//!
+//! If neither is present (or the file fails to parse), [`default_keybindings`]
+//! is used instead, so the app is always usable out of the box.
+
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::eyre;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+
+use crate::action::Action;
+
+pub const CONFIG_ENV_VAR: &str = "SQUASH_TUI_CONFIG";
+const CONFIG_FILE_NAME: &str = "keybindings.ron";
+
+/// Which panel a key chord applies to. Also doubles as the `FocusPanel`
+/// target, since focusing a panel and dispatching within it are the same
+/// three-way choice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize)]
+pub enum Panel {
+    #[default]
+    Branches,
+    Commits,
+    CommitInfo,
+}
+
+/// The active input mode `handle_key_event` dispatches against.
+pub type Mode = Panel;
+
+type RawKeybindings = HashMap<Mode, HashMap<String, Action>>;
+
+#[derive(Debug, Clone, Default)]
+pub struct KeyBindings(HashMap<Mode, HashMap<KeyEvent, Action>>);
+
+impl KeyBindings {
+    pub fn get(&self, mode: Mode, key_event: KeyEvent) -> Option<Action> {
+        self.0.get(&mode)?.get(&key_event).cloned()
+    }
+
+    /// Every chord bound in `mode`, rendered back to its `"<...>"` form
+    /// alongside the action it triggers, for the help overlay.
+    pub fn entries(&self, mode: Mode) -> Vec<(String, Action)> {
+        let mut entries: Vec<(String, Action)> = self
+            .0
+            .get(&mode)
+            .into_iter()
+            .flat_map(|bindings| bindings.iter())
+            .map(|(key_event, action)| (describe_key_event(key_event), action.clone()))
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    pub keybindings: KeyBindings,
+}
+
+impl Config {
+    /// Load the user's keybindings, falling back to [`default_keybindings`]
+    /// when no config file is found, or when one is found but fails to load
+    /// or parse (e.g. a typo'd modifier) — either way, the app stays usable.
+    pub fn load() -> color_eyre::Result<Self> {
+        let keybindings = Self::config_path()
+            .filter(|path| path.exists())
+            .and_then(|path| Self::load_file(&path).ok())
+            .unwrap_or_else(default_keybindings);
+        Ok(Self { keybindings })
+    }
+
+    fn load_file(path: &Path) -> color_eyre::Result<KeyBindings> {
+        let contents = fs::read_to_string(path)?;
+        let raw: RawKeybindings = ron::from_str(&contents)?;
+        parse_keybindings(raw)
+    }
+
+    fn config_path() -> Option<PathBuf> {
+        if let Ok(path) = env::var(CONFIG_ENV_VAR) {
+            return Some(PathBuf::from(path));
+        }
+        directories::ProjectDirs::from("", "", "squash-tui")
+            .map(|dirs| dirs.config_dir().join(CONFIG_FILE_NAME))
+    }
+}
+
+fn parse_keybindings(raw: RawKeybindings) -> color_eyre::Result<KeyBindings> {
+    let mut map = HashMap::new();
+    for (mode, chords) in raw {
+        let mut bindings = HashMap::new();
+        for (chord, action) in chords {
+            bindings.insert(parse_key_event(&chord)?, action);
+        }
+        map.insert(mode, bindings);
+    }
+    Ok(KeyBindings(map))
+}
+
+/// Parse a chord string such as `"<Ctrl-c>"`, `"<q>"`, or `"<esc>"` into a
+/// `crossterm` `KeyEvent`. The angle brackets are optional for single
+/// characters, so `"q"` works too.
+pub fn parse_key_event(raw: &str) -> color_eyre::Result<KeyEvent> {
+    let trimmed = raw.trim();
+    let inner = trimmed
+        .strip_prefix('<')
+        .and_then(|s| s.strip_suffix('>'))
+        .unwrap_or(trimmed);
+
+    let mut parts: Vec<&str> = inner.split('-').collect();
+    let key = parts
+        .pop()
+        .filter(|key| !key.is_empty())
+        .ok_or_else(|| eyre!("empty key chord: {raw:?}"))?;
+
+    let mut modifiers = KeyModifiers::NONE;
+    for modifier in parts {
+        modifiers |= match modifier.to_ascii_lowercase().as_str() {
+            "ctrl" => KeyModifiers::CONTROL,
+            "alt" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            other => return Err(eyre!("unknown modifier {other:?} in chord {raw:?}")),
+        };
+    }
+
+    let code = match key.to_ascii_lowercase().as_str() {
+        "esc" => KeyCode::Esc,
+        "enter" | "return" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "space" => KeyCode::Char(' '),
+        _ if key.chars().count() == 1 => KeyCode::Char(key.chars().next().unwrap()),
+        _ => return Err(eyre!("unrecognized key {key:?} in chord {raw:?}")),
+    };
+
+    Ok(KeyEvent::new(code, modifiers))
+}
+
+/// Render a `KeyEvent` back to the `"<...>"` chord form `parse_key_event`
+/// accepts, for display in the help overlay.
+fn describe_key_event(key_event: &KeyEvent) -> String {
+    let mut parts = Vec::new();
+    if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+        parts.push("Ctrl".to_string());
+    }
+    if key_event.modifiers.contains(KeyModifiers::ALT) {
+        parts.push("Alt".to_string());
+    }
+    if key_event.modifiers.contains(KeyModifiers::SHIFT) {
+        parts.push("Shift".to_string());
+    }
+
+    let key = match key_event.code {
+        KeyCode::Esc => "esc".to_string(),
+        KeyCode::Enter => "enter".to_string(),
+        KeyCode::Tab => "tab".to_string(),
+        KeyCode::Backspace => "backspace".to_string(),
+        KeyCode::Left => "left".to_string(),
+        KeyCode::Right => "right".to_string(),
+        KeyCode::Up => "up".to_string(),
+        KeyCode::Down => "down".to_string(),
+        KeyCode::Char(' ') => "space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        other => format!("{other:?}"),
+    };
+    parts.push(key);
+
+    format!("<{}>", parts.join("-"))
+}
+
+/// Keybindings used when no user config file is present.
+fn default_keybindings() -> KeyBindings {
+    use Action::*;
+    use Panel::*;
+
+    let mut map: HashMap<Mode, HashMap<KeyEvent, Action>> = HashMap::new();
+    for mode in [Branches, Commits, CommitInfo] {
+        let mut bindings = HashMap::new();
+        bindings.insert(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE), Quit);
+        bindings.insert(
+            KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE),
+            SelectNext,
+        );
+        bindings.insert(
+            KeyEvent::new(KeyCode::Char('k'), KeyModifiers::NONE),
+            SelectPrev,
+        );
+        bindings.insert(
+            KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE),
+            ToggleHelp,
+        );
+        map.insert(mode, bindings);
+    }
+
+    map.get_mut(&Branches)
+        .unwrap()
+        .insert(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE), FocusPanel(Commits));
+    map.get_mut(&Branches)
+        .unwrap()
+        .insert(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE), Confirm);
+    map.get_mut(&Commits)
+        .unwrap()
+        .insert(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE), FocusPanel(CommitInfo));
+    map.get_mut(&Commits)
+        .unwrap()
+        .insert(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::NONE), Squash);
+    map.get_mut(&CommitInfo)
+        .unwrap()
+        .insert(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE), FocusPanel(Branches));
+
+    KeyBindings(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_modified_chord() {
+        let key_event = parse_key_event("<Ctrl-c>").unwrap();
+        assert_eq!(key_event, KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+    }
+
+    #[test]
+    fn parses_a_bare_character_without_brackets() {
+        let key_event = parse_key_event("q").unwrap();
+        assert_eq!(key_event, KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn parses_the_named_space_key() {
+        let key_event = parse_key_event("<space>").unwrap();
+        assert_eq!(key_event, KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn rejects_an_unknown_modifier() {
+        let err = parse_key_event("<C-q>").unwrap_err();
+        assert!(err.to_string().contains("unknown modifier"));
+    }
+
+    #[test]
+    fn rejects_an_empty_chord() {
+        let err = parse_key_event("<>").unwrap_err();
+        assert!(err.to_string().contains("empty key chord"));
+    }
+
+    #[test]
+    fn describe_round_trips_through_parse() {
+        for chord in ["<Ctrl-c>", "<q>", "<space>", "<esc>", "<Ctrl-Alt-Shift-x>"] {
+            let key_event = parse_key_event(chord).unwrap();
+            let described = describe_key_event(&key_event);
+            assert_eq!(parse_key_event(&described).unwrap(), key_event);
+        }
+    }
+}
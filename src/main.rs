@@ -1,63 +1,199 @@
-use std::io;
+mod action;
+mod components;
+mod config;
+mod squash;
+mod tui;
 
-use color_eyre::{eyre::Ok, owo_colors::OwoColorize};
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
-use git2::Repository;
+use crossterm::event::KeyEvent;
 use ratatui::{
-    DefaultTerminal, Frame, border,
+    DefaultTerminal, Frame,
     layout::{Constraint, Layout, Rect},
-    symbols::border,
+    style::{Color, Modifier, Style},
     text::Line,
-    widgets::{Block, Cell, Paragraph, Widget},
+    widgets::{Block, Clear, Paragraph},
 };
 
-#[derive(Debug, Default)]
+use action::Action;
+use components::{Component, branches::Branches, commit_info::CommitInfo, commits::Commits};
+use config::{Config, Panel};
+use tui::Tui;
+
 pub struct App {
     exit: bool,
-    current_branch_name: String,
+    config: Config,
+    /// The panel the keymap currently dispatches against.
+    mode: Panel,
+    /// One per panel, in `Panel` order: Branches, Commits, CommitInfo.
+    components: Vec<Box<dyn Component>>,
+    /// Whether the keybinding help overlay is showing. While it is, input
+    /// other than toggling it back off or quitting is swallowed before it
+    /// reaches the focused panel.
+    help_visible: bool,
+    /// How many `Tick` events `Tui` emits per second.
+    tick_rate: f64,
+    /// How many `Render` events `Tui` emits per second.
+    frame_rate: f64,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self {
+            exit: false,
+            config: Config::default(),
+            mode: Panel::default(),
+            components: vec![
+                Box::new(Branches::default()),
+                Box::new(Commits::default()),
+                Box::new(CommitInfo::default()),
+            ],
+            help_visible: false,
+            tick_rate: 4.0,
+            frame_rate: 30.0,
+        }
+    }
 }
 
 impl App {
     pub fn init(&mut self) -> color_eyre::Result<()> {
         color_eyre::install()?;
-        let repo = Repository::open_from_env()?;
-        for branch_and_type in repo.branches(None)? {
-            let (branch, _type) = branch_and_type?;
-            let name = branch.name()?.unwrap();
-            print!("{name}");
+        self.config = Config::load()?;
+        for component in &mut self.components {
+            component.init()?;
         }
         Ok(())
     }
 
-    pub fn run(&mut self, terminal: &mut DefaultTerminal) -> color_eyre::Result<()> {
+    pub async fn run(&mut self, terminal: &mut DefaultTerminal) -> color_eyre::Result<()> {
+        let mut tui = Tui::new(self.tick_rate, self.frame_rate);
         while !self.exit {
-            terminal.draw(|frame| self.draw(frame))?;
-            self.handle_events()?;
+            match tui.next().await {
+                Some(tui::Event::Render) => {
+                    terminal.draw(|frame| self.draw(frame))?;
+                }
+                Some(tui::Event::Key(key_event)) => self.handle_key_event(key_event),
+                Some(tui::Event::Resize(_, _)) => {
+                    terminal.autoresize()?;
+                }
+                Some(tui::Event::Tick) => self.poll_components(),
+                Some(tui::Event::Mouse(_) | tui::Event::Paste(_)) => {}
+                None => break,
+            }
         }
         Ok(())
     }
 
     fn draw(&self, frame: &mut Frame) {
-        frame.render_widget(self, frame.area());
+        let col_constraints = (0..3).map(|_| Constraint::Length(50));
+        let row_constraints = (0..1).map(|_| Constraint::Length(100));
+        let horizontal = Layout::horizontal(col_constraints).spacing(1);
+        let vertical = Layout::vertical(row_constraints).spacing(1);
+
+        let rows = vertical.split(frame.area());
+        let cells: Vec<Rect> = rows
+            .iter()
+            .flat_map(|&row| horizontal.split(row).to_vec())
+            .collect();
+
+        for (component, area) in self.components.iter().zip(cells) {
+            component.draw(frame, area);
+        }
+
+        if self.help_visible {
+            self.draw_help(frame);
+        }
     }
 
-    fn handle_events(&mut self) -> color_eyre::Result<()> {
-        match event::read()? {
-            // it's important to check that the event is a key press event as
-            // crossterm also emits key release and repeat events on Windows.
-            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                self.handle_key_event(key_event)
+    /// A centered floating block listing every binding in the current mode,
+    /// built from the live keymap rather than a static string. The panels
+    /// underneath are dimmed first so the popup reads as modal.
+    fn draw_help(&self, frame: &mut Frame) {
+        let full_area = frame.area();
+        let buffer = frame.buffer_mut();
+        for x in full_area.left()..full_area.right() {
+            for y in full_area.top()..full_area.bottom() {
+                buffer[(x, y)].set_style(Style::default().add_modifier(Modifier::DIM));
             }
-            _ => {}
-        };
-        Ok(())
+        }
+
+        let area = centered_rect(60, 60, frame.area());
+
+        let lines: Vec<Line> = self
+            .config
+            .keybindings
+            .entries(self.mode)
+            .into_iter()
+            .map(|(chord, action)| Line::from(format!("{chord:<12} {}", action.describe())))
+            .collect();
+
+        let help = Paragraph::new(lines).style(Style::default().bg(Color::Black)).block(
+            Block::bordered().title(Line::from(format!("Help: {:?}", self.mode)).centered()),
+        );
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(help, area);
     }
 
     fn handle_key_event(&mut self, key_event: KeyEvent) {
-        match key_event.code {
-            KeyCode::Char('q') => self.exit(),
+        let Some(action) = self.config.keybindings.get(self.mode, key_event) else {
+            return;
+        };
+        // While the help overlay is open, swallow everything except what
+        // closes it (or quits outright) so it doesn't leak input through to
+        // the dimmed panels underneath.
+        if self.help_visible && !matches!(action, Action::ToggleHelp | Action::Quit) {
+            return;
+        }
+        self.apply_action(action);
+    }
+
+    fn apply_action(&mut self, action: Action) {
+        match action {
+            Action::Quit => {
+                self.exit();
+                return;
+            }
+            Action::FocusPanel(panel) => {
+                self.mode = panel;
+                return;
+            }
+            Action::ToggleHelp => {
+                self.help_visible = !self.help_visible;
+                return;
+            }
             _ => {}
         }
+
+        let focused = self.focus_index();
+        let produced = self.components[focused].handle_events(action);
+        if let Some(produced) = produced {
+            for component in &mut self.components {
+                component.update(produced.clone());
+            }
+        }
+    }
+
+    /// Give every component a chance to report background work finishing
+    /// (e.g. a squash replaying in its own thread), broadcasting whatever
+    /// `Action`s that produces the same way `apply_action` does.
+    fn poll_components(&mut self) {
+        let produced: Vec<Action> = self
+            .components
+            .iter_mut()
+            .filter_map(|component| component.poll())
+            .collect();
+        for action in produced {
+            for component in &mut self.components {
+                component.update(action.clone());
+            }
+        }
+    }
+
+    fn focus_index(&self) -> usize {
+        match self.mode {
+            Panel::Branches => 0,
+            Panel::Commits => 1,
+            Panel::CommitInfo => 2,
+        }
     }
 
     fn exit(&mut self) {
@@ -65,51 +201,34 @@ impl App {
     }
 }
 
-impl Widget for &App {
-    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer)
-    where
-        Self: Sized,
-    {
-        let col_constraints = (0..3).map(|_| Constraint::Length(50));
-        let row_constraints = (0..1).map(|_| Constraint::Length(100));
-        let horizontal = Layout::horizontal(col_constraints).spacing(1);
-        let vertical = Layout::vertical(row_constraints).spacing(1);
-
-        let rows = vertical.split(area);
-        let cells: Vec<Rect> = rows
-            .iter()
-            .flat_map(|&row| horizontal.split(row).to_vec())
-            .collect();
+/// A `percent_x` by `percent_y` rect centered within `area`.
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::vertical([
+        Constraint::Percentage((100 - percent_y) / 2),
+        Constraint::Percentage(percent_y),
+        Constraint::Percentage((100 - percent_y) / 2),
+    ])
+    .split(area);
 
-        Paragraph::new("Branches content placeholder")
-            .block(
-                Block::bordered()
-                    .title(Line::from("Branches").centered())
-                    .border_set(border::DOUBLE),
-            )
-            .render(cells[0], buf);
-        Paragraph::new("Commits content placeholder")
-            .block(
-                Block::bordered()
-                    .title(Line::from("Commits").centered())
-                    .border_set(border::DOUBLE),
-            )
-            .render(cells[1], buf);
-        Paragraph::new("Commit info placeholder")
-            .block(
-                Block::bordered()
-                    .title(Line::from("Commit info").centered())
-                    .border_set(border::DOUBLE),
-            )
-            .render(cells[2], buf);
-    }
+    Layout::horizontal([
+        Constraint::Percentage((100 - percent_x) / 2),
+        Constraint::Percentage(percent_x),
+        Constraint::Percentage((100 - percent_x) / 2),
+    ])
+    .split(vertical[1])[1]
 }
 
-fn main() -> color_eyre::Result<()> {
+#[tokio::main]
+async fn main() -> color_eyre::Result<()> {
     let mut terminal = ratatui::init();
     let mut app = App::default();
     let init_result = app.init();
-    let app_result = app.run(&mut terminal);
+    let app_result = if init_result.is_ok() {
+        app.run(&mut terminal).await
+    } else {
+        Ok(())
+    };
     ratatui::restore();
+    init_result?;
     app_result
 }
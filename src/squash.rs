@@ -0,0 +1,397 @@
+//! Core squash algorithm, implemented directly on top of `git2` (no shelling
+//! out to the `git` binary).
+//!
+//! The flow mirrors an interactive rebase `squash`, but only ever touches a
+//! single contiguous range of commits:
+//!
+//! 1. walk the branch history and locate the selected `oldest`/`newest` pair
+//! 2. build one new commit whose tree is the `newest` commit's tree, parented
+//!    on `oldest`'s parent, carrying the caller-supplied combined message
+//! 3. cherry-pick every commit that was ahead of the range back on top of it,
+//!    preserving each one's original author and message
+//! 4. move the branch ref (and HEAD, if it was checked out) to the new tip
+//!
+//! Anything that would leave the repository half-rewritten (a dirty working
+//! tree, a detached HEAD, a cherry-pick conflict) is reported as an error
+//! before any ref is moved.
+
+use std::fmt;
+
+use git2::{Commit, Oid, Repository, ResetType, Sort};
+
+/// Everything that can go wrong while squashing, surfaced to the UI as a
+/// recoverable state rather than a panic.
+#[derive(Debug)]
+pub enum SquashError {
+    /// The working tree has uncommitted changes; squashing would clobber them.
+    DirtyWorkingTree,
+    /// HEAD isn't on a branch, so there's no ref we could safely move.
+    DetachedHead,
+    /// The selected range doesn't form a contiguous slice of the branch's history.
+    RangeNotContiguous,
+    /// `oldest` has no parent (it's the root commit), so there's nothing to squash onto.
+    OldestHasNoParent,
+    /// Replaying `commit` on top of the squashed commit produced conflicts
+    /// that need to be resolved by hand.
+    CherryPickConflict { commit: Oid },
+    Git(git2::Error),
+}
+
+impl fmt::Display for SquashError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SquashError::DirtyWorkingTree => {
+                write!(f, "the working tree has uncommitted changes")
+            }
+            SquashError::DetachedHead => write!(f, "HEAD is detached"),
+            SquashError::RangeNotContiguous => {
+                write!(f, "the selected commits are not contiguous")
+            }
+            SquashError::OldestHasNoParent => {
+                write!(f, "the oldest selected commit has no parent to squash onto")
+            }
+            SquashError::CherryPickConflict { commit } => {
+                write!(f, "cherry-picking {commit} produced conflicts")
+            }
+            SquashError::Git(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for SquashError {}
+
+impl From<git2::Error> for SquashError {
+    fn from(err: git2::Error) -> Self {
+        SquashError::Git(err)
+    }
+}
+
+/// An inclusive, oldest-to-newest range of commits picked from the "Commits" panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SquashRange {
+    pub oldest: Oid,
+    pub newest: Oid,
+}
+
+/// Squash `range` on `branch_ref_name` down to a single commit with `message`,
+/// replaying any commits that were ahead of `range.newest` on top of it.
+///
+/// Returns the Oid of the new branch tip on success. On any error the repo is
+/// left exactly as it was found: refs are only moved after every replayed
+/// commit has applied cleanly.
+pub fn squash_branch_range(
+    repo: &Repository,
+    branch_ref_name: &str,
+    range: SquashRange,
+    message: &str,
+) -> Result<Oid, SquashError> {
+    ensure_clean_and_attached(repo, branch_ref_name)?;
+
+    let branch_tip = repo
+        .find_reference(branch_ref_name)?
+        .peel_to_commit()?;
+
+    let history = linear_history(repo, branch_tip.id())?;
+
+    let oldest_idx = history
+        .iter()
+        .position(|oid| *oid == range.oldest)
+        .ok_or(SquashError::RangeNotContiguous)?;
+    let newest_idx = history
+        .iter()
+        .position(|oid| *oid == range.newest)
+        .ok_or(SquashError::RangeNotContiguous)?;
+    if oldest_idx < newest_idx {
+        return Err(SquashError::RangeNotContiguous);
+    }
+
+    let oldest = repo.find_commit(range.oldest)?;
+    let newest = repo.find_commit(range.newest)?;
+    let base = oldest.parent(0).map_err(|_| SquashError::OldestHasNoParent)?;
+
+    // Commits strictly newer than `newest`, ordered oldest-first so they
+    // replay in their original order.
+    let to_replay: Vec<Oid> = history[..newest_idx].iter().rev().copied().collect();
+
+    let squashed_tree = newest.tree()?;
+    let signature = repo.signature()?;
+    let squashed_oid = repo.commit(
+        None,
+        &signature,
+        &signature,
+        message,
+        &squashed_tree,
+        &[&base],
+    )?;
+
+    let mut tip = repo.find_commit(squashed_oid)?;
+    for oid in to_replay {
+        let commit = repo.find_commit(oid)?;
+        tip = cherrypick_onto(repo, &commit, &tip)?;
+    }
+
+    let mut reference = repo.find_reference(branch_ref_name)?;
+    reference.set_target(tip.id(), "squash: rewrite history")?;
+
+    if repo.head()?.name() == Some(branch_ref_name) {
+        repo.reset(tip.as_object(), ResetType::Mixed, None)?;
+    }
+
+    Ok(tip.id())
+}
+
+/// Cherry-pick `commit` onto `onto`, carrying its original author and
+/// message, and return the resulting commit. Conflicts are reported rather
+/// than left applied with conflict markers.
+fn cherrypick_onto<'repo>(
+    repo: &'repo Repository,
+    commit: &Commit<'repo>,
+    onto: &Commit<'repo>,
+) -> Result<Commit<'repo>, SquashError> {
+    let mut index = repo.cherrypick_commit(commit, onto, 0, None)?;
+    if index.has_conflicts() {
+        return Err(SquashError::CherryPickConflict { commit: commit.id() });
+    }
+
+    let tree_oid = index.write_tree_to(repo)?;
+    let tree = repo.find_tree(tree_oid)?;
+    let committer = repo.signature()?;
+    let oid = repo.commit(
+        None,
+        &commit.author(),
+        &committer,
+        commit.message().unwrap_or_default(),
+        &tree,
+        &[onto],
+    )?;
+    Ok(repo.find_commit(oid)?)
+}
+
+/// Refuse to touch a dirty working tree or a detached HEAD before anything is rewritten.
+fn ensure_clean_and_attached(repo: &Repository, branch_ref_name: &str) -> Result<(), SquashError> {
+    let head = repo.head()?;
+    if !head.is_branch() {
+        return Err(SquashError::DetachedHead);
+    }
+    if head.name() == Some(branch_ref_name) {
+        let dirty = repo
+            .statuses(None)?
+            .iter()
+            .any(|entry| !entry.status().is_ignored());
+        if dirty {
+            return Err(SquashError::DirtyWorkingTree);
+        }
+    }
+    Ok(())
+}
+
+/// Walk `tip`'s ancestry in topological, newest-first order. The squash UI
+/// only ever offers a single linear branch of history to select from.
+fn linear_history(repo: &Repository, tip: Oid) -> Result<Vec<Oid>, git2::Error> {
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(tip)?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+    revwalk.collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use git2::Signature;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    const BRANCH: &str = "refs/heads/main";
+
+    /// A throwaway repo on disk, so each test can exercise
+    /// `squash_branch_range` against a real `git2::Repository` instead of
+    /// mocking it.
+    struct TestRepo {
+        _dir: TempDir,
+        repo: Repository,
+    }
+
+    impl TestRepo {
+        fn new() -> Self {
+            let dir = TempDir::new().expect("create tempdir");
+            let repo = Repository::init(dir.path()).expect("init repo");
+            Self { _dir: dir, repo }
+        }
+
+        fn signature(&self) -> Signature<'static> {
+            Signature::now("Test User", "test@example.com").unwrap()
+        }
+
+        /// Write `name` with `contents` and commit the whole tree onto
+        /// `BRANCH`, returning the new commit's id.
+        fn commit(&self, name: &str, contents: &str, message: &str) -> Oid {
+            fs::write(self._dir.path().join(name), contents).unwrap();
+
+            let mut index = self.repo.index().unwrap();
+            index.add_path(Path::new(name)).unwrap();
+            index.write().unwrap();
+            let tree_oid = index.write_tree().unwrap();
+            let tree = self.repo.find_tree(tree_oid).unwrap();
+
+            let parent = self
+                .repo
+                .find_reference(BRANCH)
+                .ok()
+                .and_then(|r| r.peel_to_commit().ok());
+            let signature = self.signature();
+            let parents: Vec<&Commit> = parent.iter().collect();
+            let oid = self
+                .repo
+                .commit(
+                    Some(BRANCH),
+                    &signature,
+                    &signature,
+                    message,
+                    &tree,
+                    &parents,
+                )
+                .unwrap();
+
+            if self.repo.head().is_err() {
+                self.repo.set_head(BRANCH).unwrap();
+            }
+            oid
+        }
+
+        /// Force `BRANCH` to point at `oid`, so the next `commit()` call
+        /// builds on top of it rather than the branch's current tip —
+        /// lets a test grow two divergent lines of history to cherry-pick
+        /// between.
+        fn checkout(&self, oid: Oid) {
+            self.repo.reference(BRANCH, oid, true, "test checkout").unwrap();
+        }
+    }
+
+    #[test]
+    fn squashes_a_contiguous_range_into_one_commit() {
+        let test_repo = TestRepo::new();
+        let c1 = test_repo.commit("a.txt", "1", "first");
+        let c2 = test_repo.commit("a.txt", "2", "second");
+        let c3 = test_repo.commit("a.txt", "3", "third");
+
+        let range = SquashRange {
+            oldest: c2,
+            newest: c3,
+        };
+        let new_tip = squash_branch_range(&test_repo.repo, BRANCH, range, "squashed").unwrap();
+
+        let history = linear_history(&test_repo.repo, new_tip).unwrap();
+        assert_eq!(history, vec![new_tip, c1]);
+
+        let tip_commit = test_repo.repo.find_commit(new_tip).unwrap();
+        assert_eq!(tip_commit.message(), Some("squashed"));
+        assert_eq!(tip_commit.parent_id(0).unwrap(), c1);
+    }
+
+    #[test]
+    fn replays_commits_newer_than_the_squashed_range() {
+        let test_repo = TestRepo::new();
+        let c1 = test_repo.commit("a.txt", "1", "first");
+        let c2 = test_repo.commit("a.txt", "2", "second");
+        let c3 = test_repo.commit("b.txt", "3", "third");
+
+        let range = SquashRange {
+            oldest: c1,
+            newest: c2,
+        };
+        let new_tip = squash_branch_range(&test_repo.repo, BRANCH, range, "squashed").unwrap();
+
+        let history = linear_history(&test_repo.repo, new_tip).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0], new_tip);
+
+        let replayed = test_repo.repo.find_commit(new_tip).unwrap();
+        assert_eq!(replayed.message(), Some("third"));
+        assert_ne!(replayed.id(), c3);
+    }
+
+    #[test]
+    fn refuses_a_dirty_working_tree() {
+        let test_repo = TestRepo::new();
+        let c1 = test_repo.commit("a.txt", "1", "first");
+        let c2 = test_repo.commit("a.txt", "2", "second");
+
+        fs::write(test_repo._dir.path().join("a.txt"), "dirty").unwrap();
+
+        let range = SquashRange {
+            oldest: c1,
+            newest: c2,
+        };
+        let err = squash_branch_range(&test_repo.repo, BRANCH, range, "squashed").unwrap_err();
+        assert!(matches!(err, SquashError::DirtyWorkingTree));
+    }
+
+    #[test]
+    fn refuses_a_detached_head() {
+        let test_repo = TestRepo::new();
+        let c1 = test_repo.commit("a.txt", "1", "first");
+        let c2 = test_repo.commit("a.txt", "2", "second");
+        test_repo.repo.set_head_detached(c2).unwrap();
+
+        let range = SquashRange {
+            oldest: c1,
+            newest: c2,
+        };
+        let err = squash_branch_range(&test_repo.repo, BRANCH, range, "squashed").unwrap_err();
+        assert!(matches!(err, SquashError::DetachedHead));
+    }
+
+    #[test]
+    fn refuses_a_non_contiguous_range() {
+        let test_repo = TestRepo::new();
+        let c1 = test_repo.commit("a.txt", "1", "first");
+        let _c2 = test_repo.commit("a.txt", "2", "second");
+        let c3 = test_repo.commit("a.txt", "3", "third");
+
+        // `oldest` is newer than `newest` here, so the pair doesn't bound a
+        // valid oldest-to-newest slice of history.
+        let range = SquashRange {
+            oldest: c3,
+            newest: c1,
+        };
+        let err = squash_branch_range(&test_repo.repo, BRANCH, range, "squashed").unwrap_err();
+        assert!(matches!(err, SquashError::RangeNotContiguous));
+    }
+
+    #[test]
+    fn refuses_when_the_oldest_commit_has_no_parent() {
+        let test_repo = TestRepo::new();
+        let root = test_repo.commit("a.txt", "1", "root");
+        let tip = test_repo.commit("a.txt", "2", "second");
+
+        let range = SquashRange {
+            oldest: root,
+            newest: tip,
+        };
+        let err = squash_branch_range(&test_repo.repo, BRANCH, range, "squashed").unwrap_err();
+        assert!(matches!(err, SquashError::OldestHasNoParent));
+    }
+
+    // `squash_branch_range` only ever walks a single linear branch, so the
+    // first replayed commit's original parent (`newest`) always has exactly
+    // the tree of the squashed tip it's replayed onto — a genuine conflict needs `onto` to
+    // have diverged from the replayed commit's parent, which we exercise
+    // directly against `cherrypick_onto` below.
+    #[test]
+    fn cherrypick_onto_reports_conflicts_without_applying_them() {
+        let test_repo = TestRepo::new();
+        let base = test_repo.commit("a.txt", "base", "base");
+        test_repo.checkout(base);
+        let theirs = test_repo.commit("a.txt", "theirs", "theirs");
+        test_repo.checkout(base);
+        let ours = test_repo.commit("a.txt", "ours", "ours");
+
+        let ours_commit = test_repo.repo.find_commit(ours).unwrap();
+        let theirs_commit = test_repo.repo.find_commit(theirs).unwrap();
+
+        let err = cherrypick_onto(&test_repo.repo, &theirs_commit, &ours_commit).unwrap_err();
+        assert!(matches!(err, SquashError::CherryPickConflict { commit } if commit == theirs));
+    }
+}
@@ -0,0 +1,91 @@
+//! Decouples terminal input from rendering. Instead of blocking on
+//! `crossterm::event::read()`, a background task `tokio::select!`s over a
+//! `crossterm::event::EventStream` and two interval timers, emitting a single
+//! internal [`Event`] onto an mpsc channel. `App::run` just drains that
+//! channel, so the UI can repaint on a steady cadence (needed for progress
+//! while a squash replays commits) without losing input responsiveness.
+
+use std::time::Duration;
+
+use crossterm::event::{
+    Event as CrosstermEvent, EventStream, KeyEvent, KeyEventKind, MouseEvent,
+};
+use futures::{FutureExt, StreamExt};
+use tokio::{sync::mpsc, task::JoinHandle};
+
+#[derive(Clone, Debug)]
+pub enum Event {
+    Tick,
+    Render,
+    Key(KeyEvent),
+    Mouse(MouseEvent),
+    Resize(u16, u16),
+    Paste(String),
+}
+
+/// Owns the background task that turns terminal input plus tick/render
+/// timers into a single stream of [`Event`]s.
+pub struct Tui {
+    rx: mpsc::UnboundedReceiver<Event>,
+    task: JoinHandle<()>,
+}
+
+impl Tui {
+    /// `tick_rate` and `frame_rate` are both in Hz.
+    pub fn new(tick_rate: f64, frame_rate: f64) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let task = tokio::spawn(Self::event_loop(tx, tick_rate, frame_rate));
+        Self { rx, task }
+    }
+
+    /// Await the next event, whichever of input/tick/render fires first.
+    pub async fn next(&mut self) -> Option<Event> {
+        self.rx.recv().await
+    }
+
+    async fn event_loop(tx: mpsc::UnboundedSender<Event>, tick_rate: f64, frame_rate: f64) {
+        let mut reader = EventStream::new();
+        let mut tick_interval = tokio::time::interval(Duration::from_secs_f64(1.0 / tick_rate));
+        let mut render_interval = tokio::time::interval(Duration::from_secs_f64(1.0 / frame_rate));
+
+        loop {
+            let tick_delay = tick_interval.tick();
+            let render_delay = render_interval.tick();
+            let crossterm_event = reader.next().fuse();
+
+            let event = tokio::select! {
+                maybe_event = crossterm_event => match maybe_event {
+                    Some(Ok(event)) => Self::map_crossterm_event(event),
+                    Some(Err(_)) | None => return,
+                },
+                _ = tick_delay => Some(Event::Tick),
+                _ = render_delay => Some(Event::Render),
+            };
+
+            if let Some(event) = event {
+                if tx.send(event).is_err() {
+                    return;
+                }
+            }
+        }
+    }
+
+    fn map_crossterm_event(event: CrosstermEvent) -> Option<Event> {
+        match event {
+            // it's important to check that the event is a key press event as
+            // crossterm also emits key release and repeat events on Windows.
+            CrosstermEvent::Key(key) if key.kind == KeyEventKind::Press => Some(Event::Key(key)),
+            CrosstermEvent::Key(_) => None,
+            CrosstermEvent::Mouse(mouse) => Some(Event::Mouse(mouse)),
+            CrosstermEvent::Resize(width, height) => Some(Event::Resize(width, height)),
+            CrosstermEvent::Paste(text) => Some(Event::Paste(text)),
+            CrosstermEvent::FocusGained | CrosstermEvent::FocusLost => None,
+        }
+    }
+}
+
+impl Drop for Tui {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}